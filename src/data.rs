@@ -0,0 +1,243 @@
+//! # Byte-Payload Encoding and Data Availability
+//!
+//! Encodes arbitrary byte payloads as polynomials over `E::ScalarField` so
+//! they can be witness-encrypted with [`crate::we::WE`], and layers a
+//! Reed-Solomon data-availability mode on top: the payload is committed
+//! once, evaluated at more points than its degree, and each evaluation is
+//! independently witness-encrypted. Any `k` correctly-opened cells are
+//! enough to reconstruct the whole payload.
+
+use crate::kzg::KZGError;
+use crate::pol_op::{evaluate_polynomial, lagrange_interpolate, points_are_distinct};
+use crate::we::{WEError, WE};
+use ark_ec::pairing::Pairing;
+use ark_ff::{BigInteger, PrimeField};
+use thiserror::Error;
+
+/// Maximum number of payload bytes packed into a single scalar limb.
+///
+/// 31 bytes (248 bits) fits comfortably under the BLS12-381 scalar field
+/// modulus, which is just under 2^255.
+pub const CHUNK_SIZE: usize = 31;
+
+/// Splits `data` into `CHUNK_SIZE`-byte little-endian limbs, each encoded as
+/// a scalar field element. The final limb instead carries up to
+/// `CHUNK_SIZE - 1` data bytes prefixed with its own valid byte length, so
+/// that every limb (including the final one) stays within `CHUNK_SIZE`
+/// bytes and [`polynomial_to_bytes`] can recover the exact original length.
+pub fn bytes_to_polynomial<E: Pairing>(data: &[u8]) -> Vec<E::ScalarField> {
+    let full_chunks = data.len() / CHUNK_SIZE;
+    let remainder = &data[full_chunks * CHUNK_SIZE..];
+
+    let mut coeffs = Vec::with_capacity(full_chunks + 1);
+    for chunk in data[..full_chunks * CHUNK_SIZE].chunks(CHUNK_SIZE) {
+        let mut buf = [0u8; CHUNK_SIZE];
+        buf.copy_from_slice(chunk);
+        coeffs.push(E::ScalarField::from_le_bytes_mod_order(&buf));
+    }
+
+    let mut buf = [0u8; CHUNK_SIZE];
+    buf[0] = remainder.len() as u8;
+    buf[1..1 + remainder.len()].copy_from_slice(remainder);
+    coeffs.push(E::ScalarField::from_le_bytes_mod_order(&buf));
+
+    coeffs
+}
+
+/// Inverse of [`bytes_to_polynomial`]: reassembles the original byte
+/// payload from its polynomial coefficients.
+pub fn polynomial_to_bytes<E: Pairing>(coeffs: &[E::ScalarField]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(coeffs.len() * CHUNK_SIZE);
+    let last = coeffs.len() - 1;
+
+    for (i, coeff) in coeffs.iter().enumerate() {
+        let bytes = coeff.into_bigint().to_bytes_le();
+        if i == last {
+            let len = bytes[0] as usize;
+            data.extend_from_slice(&bytes[1..1 + len]);
+        } else {
+            data.extend_from_slice(&bytes[..CHUNK_SIZE]);
+        }
+    }
+
+    data
+}
+
+/// A single witness-encrypted, erasure-coded shard of a payload: the
+/// evaluation of the payload's polynomial at `point`, unlockable by a KZG
+/// opening at that point.
+pub struct Shard<E: Pairing> {
+    pub point: E::ScalarField,
+    pub key_ct: E::G2,
+    pub msg_ct: Vec<u8>,
+}
+
+/// Encodes `data` as a degree `< k` polynomial, commits to it once, and
+/// witness-encrypts its evaluation at each of `eval_points` (which must be
+/// longer than the number of chunks `data` encodes to, for redundancy).
+///
+/// Returns the commitment and one [`Shard`] per evaluation point.
+pub fn encode<E: Pairing>(
+    we: &WE<E>,
+    data: &[u8],
+    eval_points: &[E::ScalarField],
+) -> Result<(E::G1, Vec<Shard<E>>), DataError> {
+    let coeffs = bytes_to_polynomial::<E>(data);
+    if eval_points.len() <= coeffs.len() {
+        return Err(DataError::NotEnoughEvaluationPoints);
+    }
+
+    let com = we.kem().kzg().commit(&coeffs)?;
+
+    let shards = eval_points
+        .iter()
+        .map(|&point| {
+            let value = evaluate_polynomial::<E>(&coeffs, &point);
+            let value_bytes = value.into_bigint().to_bytes_le();
+            let (key_ct, msg_ct) = we.encrypt_single(com, point, value, &value_bytes)?;
+            Ok(Shard { point, key_ct, msg_ct })
+        })
+        .collect::<Result<Vec<_>, DataError>>()?;
+
+    Ok((com, shards))
+}
+
+/// Decrypts a single shard using a KZG opening at its point, recovering the
+/// evaluation value it carries.
+pub fn decrypt_shard<E: Pairing>(
+    we: &WE<E>,
+    shard: &Shard<E>,
+    proof: E::G1,
+) -> Result<E::ScalarField, DataError> {
+    let value_bytes = we.decrypt_single(proof, shard.key_ct, &shard.msg_ct)?;
+    Ok(E::ScalarField::from_le_bytes_mod_order(&value_bytes))
+}
+
+/// Reconstructs the original payload from `k` or more recovered
+/// `(point, value)` cells, via Lagrange interpolation.
+pub fn decode<E: Pairing>(k: usize, cells: &[(E::ScalarField, E::ScalarField)]) -> Result<Vec<u8>, DataError> {
+    if cells.len() < k {
+        return Err(DataError::InsufficientCells);
+    }
+
+    let points: Vec<E::ScalarField> = cells[..k].iter().map(|(p, _)| *p).collect();
+    let values: Vec<E::ScalarField> = cells[..k].iter().map(|(_, v)| *v).collect();
+
+    if !points_are_distinct(&points) {
+        return Err(DataError::DuplicateEvaluationPoint);
+    }
+
+    let coeffs = lagrange_interpolate::<E>(&points, &values);
+    Ok(polynomial_to_bytes::<E>(&coeffs))
+}
+
+#[derive(Error, Debug)]
+pub enum DataError {
+    #[error("Witness Encryption Error {0}")]
+    WEError(WEError),
+    #[error("KZG Error {0}")]
+    KZGError(KZGError),
+    #[error("Not enough evaluation points were supplied for the payload's degree")]
+    NotEnoughEvaluationPoints,
+    #[error("Fewer than the required number of cells were recovered")]
+    InsufficientCells,
+    #[error("Two or more recovered cells share the same evaluation point")]
+    DuplicateEvaluationPoint,
+}
+
+impl From<WEError> for DataError {
+    fn from(error: WEError) -> Self {
+        DataError::WEError(error)
+    }
+}
+
+impl From<KZGError> for DataError {
+    fn from(error: KZGError) -> Self {
+        DataError::KZGError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kem::KEM;
+    use crate::kzg::KZG;
+    use ark_bls12_381::{Bls12_381, Fr, G1Projective, G2Projective};
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn test_bytes_to_polynomial_roundtrip() {
+        let data = b"the quick brown fox jumps over the lazy dog, sixty-three bytes!".to_vec();
+
+        let coeffs = bytes_to_polynomial::<Bls12_381>(&data);
+        let recovered = polynomial_to_bytes::<Bls12_381>(&coeffs);
+
+        assert_eq!(data, recovered);
+    }
+
+    #[test]
+    fn test_bytes_to_polynomial_roundtrip_on_exact_chunk_multiples() {
+        // Regression test: when `data.len()` is an exact, nonzero multiple
+        // of CHUNK_SIZE, the final limb used to be encoded as a full
+        // CHUNK_SIZE-byte chunk plus a length-prefix byte (32 bytes total),
+        // which can exceed the scalar field modulus and get silently
+        // reduced, corrupting the recovered length prefix and truncating
+        // the payload.
+        for len in [CHUNK_SIZE, 2 * CHUNK_SIZE, 3 * CHUNK_SIZE] {
+            let data: Vec<u8> = (0..len).map(|i| 0xFF - (i % 256) as u8).collect();
+
+            let coeffs = bytes_to_polynomial::<Bls12_381>(&data);
+            let recovered = polynomial_to_bytes::<Bls12_381>(&coeffs);
+
+            assert_eq!(data, recovered, "roundtrip failed for len {len}");
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_recovers_payload_from_any_k_shards() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 20;
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        let data = b"erasure coded witness-encrypted data availability layer".to_vec();
+        let k = bytes_to_polynomial::<Bls12_381>(&data).len();
+
+        let eval_points: Vec<Fr> = (0..k + 3).map(|_| Fr::rand(rng)).collect();
+        let (com, shards) = encode(&we, &data, &eval_points).unwrap();
+
+        let cells: Vec<(Fr, Fr)> = shards
+            .iter()
+            .map(|shard| {
+                let proof = we
+                    .kem()
+                    .kzg()
+                    .open(&bytes_to_polynomial::<Bls12_381>(&data), &shard.point)
+                    .unwrap();
+                let value = decrypt_shard(&we, shard, proof).unwrap();
+                (shard.point, value)
+            })
+            .collect();
+
+        // Drop down to exactly `k` cells, a subset of the full redundant set.
+        let recovered = decode::<Bls12_381>(k, &cells[..k]).unwrap();
+
+        assert_eq!(data, recovered);
+        assert_eq!(we.kem().kzg().commit(&bytes_to_polynomial::<Bls12_381>(&data)).unwrap(), com);
+    }
+
+    #[test]
+    fn test_decode_rejects_duplicate_evaluation_points() {
+        let rng = &mut test_rng();
+        let point = Fr::rand(rng);
+        let cells = vec![(point, Fr::rand(rng)), (point, Fr::rand(rng))];
+
+        let result = decode::<Bls12_381>(2, &cells);
+        assert!(matches!(result, Err(DataError::DuplicateEvaluationPoint)));
+    }
+}
@@ -0,0 +1,196 @@
+//! # Hybrid Witness + Post-Quantum KEM Encryption
+//!
+//! Gated behind the `pq` feature so the core crate stays pairing-only.
+//!
+//! Combines the pairing-based witness KEM with an ML-KEM (Kyber)
+//! encapsulation addressed to a specific recipient, so that decryption
+//! requires *both* a valid KZG witness *and* the recipient's Kyber secret
+//! key. A ciphertext produced this way stays confidential even if the
+//! pairing-based witness relation is later broken by a quantum adversary.
+
+use crate::kem::KeyStream;
+use crate::we::{authentication_tag, tags_match, WEError, WE};
+use ark_ec::pairing::Pairing;
+use pqcrypto_kyber::kyber768::{decapsulate, encapsulate, PublicKey, SecretKey};
+use pqcrypto_traits::kem::{Ciphertext, SharedSecret};
+use sha2::{Digest, Sha256};
+
+/// A message encrypted under [`encrypt_hybrid`], recoverable only via
+/// [`decrypt_hybrid`] with both a KZG witness for `key_ct` and the Kyber
+/// secret key matching the recipient public key it was created for.
+pub struct HybridCiphertext<E: Pairing> {
+    /// The witness-KEM key ciphertext.
+    pub key_ct: E::G2,
+    /// The Kyber (ML-KEM) encapsulation ciphertext.
+    pub kyber_ct: Vec<u8>,
+    /// `msg` encrypted under `KDF(k_we || k_pq)`.
+    pub msg_ct: Vec<u8>,
+    /// Authentication tag over `msg_ct`.
+    pub tag: Vec<u8>,
+}
+
+/// Encrypts `msg` so that recovering it requires both a valid KZG witness
+/// for `(com, point, value)` and `recipient_pk`'s matching Kyber secret key.
+pub fn encrypt_hybrid<E: Pairing>(
+    we: &WE<E>,
+    com: E::G1,
+    point: E::ScalarField,
+    value: E::ScalarField,
+    recipient_pk: &PublicKey,
+    msg: &[u8],
+) -> Result<HybridCiphertext<E>, WEError> {
+    let (key_ct, mut we_key_stream) = we.kem().encapsulate(com, point, value)?;
+    let mut k_we = [0u8; 32];
+    we_key_stream.fill(&mut k_we);
+
+    let (k_pq, kyber_ct) = encapsulate(recipient_pk);
+
+    let combined_key = combine_keys(&k_we, k_pq.as_bytes());
+    let mut key_stream = KeyStream::from_seed_bytes(&combined_key);
+
+    let mut enc_keystream = vec![0u8; msg.len()];
+    key_stream.fill(&mut enc_keystream);
+    let mut mac_key = [0u8; 32];
+    key_stream.fill(&mut mac_key);
+
+    let mut msg_ct = vec![0u8; msg.len()];
+    for i in 0..msg.len() {
+        msg_ct[i] = msg[i] ^ enc_keystream[i];
+    }
+
+    let tag = authentication_tag(&mac_key, &msg_ct);
+
+    Ok(HybridCiphertext {
+        key_ct,
+        kyber_ct: kyber_ct.as_bytes().to_vec(),
+        msg_ct,
+        tag,
+    })
+}
+
+/// Decrypts a [`HybridCiphertext`] given a KZG witness `proof` for `ct.key_ct`
+/// and the recipient's Kyber secret key.
+pub fn decrypt_hybrid<E: Pairing>(
+    we: &WE<E>,
+    proof: E::G1,
+    ct: &HybridCiphertext<E>,
+    recipient_sk: &SecretKey,
+) -> Result<Vec<u8>, WEError> {
+    let mut we_key_stream = we.kem().decapsulate(proof, ct.key_ct)?;
+    let mut k_we = [0u8; 32];
+    we_key_stream.fill(&mut k_we);
+
+    let kyber_ct = pqcrypto_kyber::kyber768::Ciphertext::from_bytes(&ct.kyber_ct)
+        .map_err(|_| WEError::AuthenticationFailed)?;
+    let k_pq = decapsulate(&kyber_ct, recipient_sk);
+
+    let combined_key = combine_keys(&k_we, k_pq.as_bytes());
+    let mut key_stream = KeyStream::from_seed_bytes(&combined_key);
+
+    let mut enc_keystream = vec![0u8; ct.msg_ct.len()];
+    key_stream.fill(&mut enc_keystream);
+    let mut mac_key = [0u8; 32];
+    key_stream.fill(&mut mac_key);
+
+    let expected_tag = authentication_tag(&mac_key, &ct.msg_ct);
+    if !tags_match(&expected_tag, &ct.tag) {
+        return Err(WEError::AuthenticationFailed);
+    }
+
+    let mut msg = vec![0u8; ct.msg_ct.len()];
+    for i in 0..ct.msg_ct.len() {
+        msg[i] = ct.msg_ct[i] ^ enc_keystream[i];
+    }
+
+    Ok(msg)
+}
+
+/// `KDF(k_we || k_pq)`, combining the witness-KEM key and the Kyber shared
+/// secret into the final message key.
+fn combine_keys(k_we: &[u8], k_pq: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"keaki-hybrid-kdf");
+    hasher.update(k_we);
+    hasher.update(k_pq);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kem::KEM;
+    use crate::kzg::KZG;
+    use crate::pol_op::evaluate_polynomial;
+    use ark_bls12_381::{Bls12_381, Fr, G1Projective, G2Projective};
+    use ark_std::test_rng;
+    use ark_std::UniformRand;
+    use pqcrypto_kyber::kyber768::keypair;
+
+    #[test]
+    fn test_encrypt_decrypt_hybrid() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let point: Fr = Fr::rand(rng);
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        // p(x) = 7 x^4 + 9 x^3 - 5 x^2 - 25 x - 24
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let val = evaluate_polynomial::<Bls12_381>(&p, &point);
+        let commitment = we.kem().kzg().commit(&p).unwrap();
+
+        let (recipient_pk, recipient_sk) = keypair();
+
+        let msg = b"helloworld";
+        let ct = encrypt_hybrid(&we, commitment, point, val, &recipient_pk, msg).unwrap();
+
+        let proof = we.kem().kzg().open(&p, &point).unwrap();
+
+        let decrypted_msg = decrypt_hybrid(&we, proof, &ct, &recipient_sk).unwrap();
+        assert_eq!(msg.to_vec(), decrypted_msg);
+    }
+
+    #[test]
+    fn test_decrypt_hybrid_wrong_secret_key_fails() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let point: Fr = Fr::rand(rng);
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let val = evaluate_polynomial::<Bls12_381>(&p, &point);
+        let commitment = we.kem().kzg().commit(&p).unwrap();
+
+        let (recipient_pk, _) = keypair();
+        let (_, wrong_sk) = keypair();
+
+        let msg = b"helloworld";
+        let ct = encrypt_hybrid(&we, commitment, point, val, &recipient_pk, msg).unwrap();
+
+        let proof = we.kem().kzg().open(&p, &point).unwrap();
+
+        let result = decrypt_hybrid(&we, proof, &ct, &wrong_sk);
+        assert!(matches!(result, Err(WEError::AuthenticationFailed)));
+    }
+}
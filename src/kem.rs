@@ -0,0 +1,148 @@
+//! # Extractable Witness KEM
+//!
+//! A key encapsulation mechanism extractable from a KZG opening: anyone
+//! holding a valid witness for `com` opening to `value` at `point` can
+//! recover the encapsulated key, and no one else can.
+
+use crate::kzg::{KZGError, KZG};
+use crate::pol_op::{lagrange_interpolate, points_are_distinct, vanishing_polynomial};
+use ark_ec::pairing::Pairing;
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::thread_rng;
+use ark_std::UniformRand;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// KZG-based extractable witness KEM.
+pub struct KEM<E: Pairing> {
+    kzg: KZG<E>,
+}
+
+impl<E: Pairing> KEM<E> {
+    /// Create a new instance wrapping a KZG setup.
+    pub fn new(kzg: KZG<E>) -> Self {
+        Self { kzg }
+    }
+
+    /// The underlying KZG instance.
+    pub fn kzg(&self) -> &KZG<E> {
+        &self.kzg
+    }
+
+    /// Encapsulates a key bound to the statement "`com` opens to `value` at
+    /// `point`". Returns the key ciphertext and the keystream; only a party
+    /// that later supplies a valid KZG witness can decapsulate it.
+    pub fn encapsulate(
+        &self,
+        com: E::G1,
+        point: E::ScalarField,
+        value: E::ScalarField,
+    ) -> Result<(E::G2, KeyStream), KEMError> {
+        let r = E::ScalarField::rand(&mut thread_rng());
+
+        let key_ct = (self.kzg.tau_g2() - self.kzg.g2_gen() * point) * r;
+        let shared = E::pairing(com - self.kzg.g1_gen() * value, self.kzg.g2_gen() * r);
+
+        Ok((key_ct, KeyStream::from_pairing_output(shared)))
+    }
+
+    /// Decapsulates the key using a KZG witness `proof` for the key
+    /// ciphertext `key_ct`.
+    pub fn decapsulate(&self, proof: E::G1, key_ct: E::G2) -> Result<KeyStream, KEMError> {
+        let shared = E::pairing(proof, key_ct);
+        Ok(KeyStream::from_pairing_output(shared))
+    }
+
+    /// Encapsulates a key bound to the statement "`com` opens to `values[i]`
+    /// at `points[i]`, for every `i`". Decapsulatable by a single aggregated
+    /// KZG batch opening over the whole set of points.
+    pub fn encapsulate_batch(
+        &self,
+        com: E::G1,
+        points: &[E::ScalarField],
+        values: &[E::ScalarField],
+    ) -> Result<(E::G2, KeyStream), KEMError> {
+        if !points_are_distinct(points) {
+            return Err(KEMError::DuplicatePoint);
+        }
+
+        let r_poly = lagrange_interpolate::<E>(points, values);
+        let z_s = vanishing_polynomial::<E>(points);
+
+        let r_com = self.kzg.commit(&r_poly)?;
+        let z_s_g2 = self.kzg.commit_g2(&z_s)?;
+
+        let r = E::ScalarField::rand(&mut thread_rng());
+        let key_ct = z_s_g2 * r;
+        let shared = E::pairing(com - r_com, self.kzg.g2_gen() * r);
+
+        Ok((key_ct, KeyStream::from_pairing_output(shared)))
+    }
+
+    /// Decapsulates the key using a single aggregated KZG batch witness
+    /// `proof` for the key ciphertext `key_ct`.
+    pub fn decapsulate_batch(&self, proof: E::G1, key_ct: E::G2) -> Result<KeyStream, KEMError> {
+        self.decapsulate(proof, key_ct)
+    }
+}
+
+/// A pseudorandom byte stream derived from a shared pairing output, used to
+/// one-time-pad encrypt/decrypt a message (and, in the authenticated mode,
+/// to derive a MAC key alongside it).
+pub struct KeyStream {
+    seed: [u8; 32],
+    counter: u64,
+}
+
+impl KeyStream {
+    fn from_pairing_output<T: CanonicalSerialize>(output: T) -> Self {
+        let mut bytes = Vec::new();
+        output
+            .serialize_compressed(&mut bytes)
+            .expect("serialization of a pairing output cannot fail");
+
+        Self::from_seed_bytes(&bytes)
+    }
+
+    /// Builds a keystream directly from a seed (e.g. a symmetric key that
+    /// was not itself derived from a pairing, as in threshold encryption).
+    pub fn from_seed_bytes(bytes: &[u8]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"keaki-kem-keystream");
+        hasher.update(bytes);
+
+        Self {
+            seed: hasher.finalize().into(),
+            counter: 0,
+        }
+    }
+
+    /// Fills `buf` with pseudorandom bytes derived from the shared secret.
+    pub fn fill(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(32) {
+            let mut hasher = Sha256::new();
+            hasher.update(self.seed);
+            hasher.update(self.counter.to_le_bytes());
+            self.counter += 1;
+
+            let block = hasher.finalize();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum KEMError {
+    #[error("Invalid statement: the supplied point/value is out of range for this SRS")]
+    InvalidStatement,
+    #[error("KZG Error {0}")]
+    KZGError(KZGError),
+    #[error("Two or more of the supplied points are equal")]
+    DuplicatePoint,
+}
+
+impl From<KZGError> for KEMError {
+    fn from(error: KZGError) -> Self {
+        KEMError::KZGError(error)
+    }
+}
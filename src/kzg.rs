@@ -0,0 +1,171 @@
+//! # KZG Polynomial Commitment Scheme
+//!
+//! A minimal implementation of the Kate-Zaverucha-Goldberg polynomial
+//! commitment scheme over a pairing-friendly curve, used as the building
+//! block for the witness KEM in [`crate::kem`].
+
+use crate::pol_op::{
+    divide_by_linear, divide_polynomials, evaluate_polynomial, lagrange_interpolate,
+    points_are_distinct, subtract_polynomials, vanishing_polynomial,
+};
+use ark_ec::pairing::Pairing;
+use thiserror::Error;
+
+/// Structured reference string and commitment/opening logic for KZG.
+pub struct KZG<E: Pairing> {
+    g1_gen: E::G1,
+    g2_gen: E::G2,
+    /// `[g1^{tau^0}, g1^{tau^1}, ..., g1^{tau^max_degree}]`
+    powers_of_g1: Vec<E::G1>,
+    /// `[g2^{tau^0}, g2^{tau^1}, ..., g2^{tau^max_degree}]`
+    powers_of_g2: Vec<E::G2>,
+}
+
+impl<E: Pairing> KZG<E> {
+    /// Runs the trusted setup for a given maximum polynomial degree.
+    ///
+    /// `secret` (`tau`) must be discarded by the caller after this returns;
+    /// it is only used here to derive the public powers-of-tau SRS.
+    pub fn setup(g1_gen: E::G1, g2_gen: E::G2, max_degree: usize, secret: E::ScalarField) -> Self {
+        let mut powers_of_g1 = Vec::with_capacity(max_degree + 1);
+        let mut powers_of_g2 = Vec::with_capacity(max_degree + 1);
+
+        let mut power = E::ScalarField::from(1u64);
+        for _ in 0..=max_degree {
+            powers_of_g1.push(g1_gen * power);
+            powers_of_g2.push(g2_gen * power);
+            power *= secret;
+        }
+
+        Self {
+            g1_gen,
+            g2_gen,
+            powers_of_g1,
+            powers_of_g2,
+        }
+    }
+
+    /// The generator of G1 used by this SRS.
+    pub fn g1_gen(&self) -> E::G1 {
+        self.g1_gen
+    }
+
+    /// The generator of G2 used by this SRS.
+    pub fn g2_gen(&self) -> E::G2 {
+        self.g2_gen
+    }
+
+    /// `[tau]_2`, the first non-trivial power of tau in G2.
+    pub fn tau_g2(&self) -> E::G2 {
+        self.powers_of_g2[1]
+    }
+
+    /// Commits to a polynomial `p`, given as coefficients (lowest degree first).
+    pub fn commit(&self, p: &[E::ScalarField]) -> Result<E::G1, KZGError> {
+        if p.len() > self.powers_of_g1.len() {
+            return Err(KZGError::PolynomialTooLarge);
+        }
+
+        Ok(p.iter()
+            .zip(self.powers_of_g1.iter())
+            .map(|(coeff, power)| *power * coeff)
+            .sum())
+    }
+
+    /// Opens a commitment to `p` at `point`, returning the KZG witness.
+    pub fn open(&self, p: &[E::ScalarField], point: &E::ScalarField) -> Result<E::G1, KZGError> {
+        let value = evaluate_polynomial::<E>(p, point);
+        let quotient = divide_by_linear::<E>(p, point, &value);
+        self.commit(&quotient)
+    }
+
+    /// Verifies a single-point opening: `com` opens to `value` at `point`
+    /// with witness `proof`.
+    pub fn verify(
+        &self,
+        com: E::G1,
+        point: E::ScalarField,
+        value: E::ScalarField,
+        proof: E::G1,
+    ) -> bool {
+        let lhs = E::pairing(com - self.g1_gen * value, self.g2_gen);
+        let rhs = E::pairing(proof, self.tau_g2() - self.g2_gen * point);
+        lhs == rhs
+    }
+
+    /// Commits to a polynomial in G2, given as coefficients (lowest degree
+    /// first). Used to commit to the vanishing polynomial `Z_S(x)` for a
+    /// batch opening.
+    pub fn commit_g2(&self, p: &[E::ScalarField]) -> Result<E::G2, KZGError> {
+        if p.len() > self.powers_of_g2.len() {
+            return Err(KZGError::PolynomialTooLarge);
+        }
+
+        Ok(p.iter()
+            .zip(self.powers_of_g2.iter())
+            .map(|(coeff, power)| *power * coeff)
+            .sum())
+    }
+
+    /// Opens a commitment to `p` at a set of `points` with a single
+    /// aggregated witness.
+    ///
+    /// Let `Z_S(x) = \prod_i (x - points[i])` and `r(x)` be the degree
+    /// `< points.len()` interpolation through `(points[i], p(points[i]))`.
+    /// The witness is `Commit((p(x) - r(x)) / Z_S(x))`.
+    pub fn open_batch(
+        &self,
+        p: &[E::ScalarField],
+        points: &[E::ScalarField],
+    ) -> Result<E::G1, KZGError> {
+        if !points_are_distinct(points) {
+            return Err(KZGError::DuplicatePoint);
+        }
+
+        let values: Vec<E::ScalarField> = points
+            .iter()
+            .map(|point| evaluate_polynomial::<E>(p, point))
+            .collect();
+
+        let r = lagrange_interpolate::<E>(points, &values);
+        let z_s = vanishing_polynomial::<E>(points);
+        let numerator = subtract_polynomials::<E>(p, &r);
+        let quotient = divide_polynomials::<E>(&numerator, &z_s);
+
+        self.commit(&quotient)
+    }
+
+    /// Verifies a batch opening: `com` opens to `values[i]` at `points[i]`
+    /// for every `i`, with aggregated witness `proof`.
+    ///
+    /// Checks `e(com - [r(s)]_1, g2) == e(proof, [Z_S(s)]_2)`.
+    pub fn verify_batch(
+        &self,
+        com: E::G1,
+        points: &[E::ScalarField],
+        values: &[E::ScalarField],
+        proof: E::G1,
+    ) -> Result<bool, KZGError> {
+        if !points_are_distinct(points) {
+            return Err(KZGError::DuplicatePoint);
+        }
+
+        let r = lagrange_interpolate::<E>(points, values);
+        let z_s = vanishing_polynomial::<E>(points);
+
+        let r_com = self.commit(&r)?;
+        let z_s_g2 = self.commit_g2(&z_s)?;
+
+        let lhs = E::pairing(com - r_com, self.g2_gen);
+        let rhs = E::pairing(proof, z_s_g2);
+        Ok(lhs == rhs)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum KZGError {
+    #[error("Polynomial degree exceeds the SRS maximum degree")]
+    PolynomialTooLarge,
+    #[error("Two or more of the supplied points are equal")]
+    DuplicatePoint,
+}
@@ -0,0 +1,12 @@
+//! # keaki
+//!
+//! An implementation of Extractable Witness Encryption built on top of KZG
+//! polynomial commitments.
+
+pub mod data;
+#[cfg(feature = "pq")]
+pub mod hybrid;
+pub mod kem;
+pub mod kzg;
+pub mod pol_op;
+pub mod we;
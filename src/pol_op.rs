@@ -0,0 +1,178 @@
+//! # Polynomial Operations
+//!
+//! Small helpers for working with dense polynomials represented as
+//! coefficient vectors (lowest degree first), as used by [`crate::kzg::KZG`].
+
+use ark_ec::pairing::Pairing;
+use ark_ff::Field;
+
+/// Evaluates a polynomial `p` (coefficients, lowest degree first) at `point`.
+pub fn evaluate_polynomial<E: Pairing>(p: &[E::ScalarField], point: &E::ScalarField) -> E::ScalarField {
+    let mut result = E::ScalarField::from(0u64);
+    let mut power = E::ScalarField::from(1u64);
+
+    for coeff in p {
+        result += *coeff * power;
+        power *= point;
+    }
+
+    result
+}
+
+/// Divides `p(x) - value` by the linear factor `(x - point)`.
+///
+/// Assumes `p(point) == value`, i.e. that the division is exact; the
+/// remainder is dropped otherwise.
+pub fn divide_by_linear<E: Pairing>(
+    p: &[E::ScalarField],
+    point: &E::ScalarField,
+    value: &E::ScalarField,
+) -> Vec<E::ScalarField> {
+    let mut shifted = p.to_vec();
+    if let Some(first) = shifted.first_mut() {
+        *first -= value;
+    }
+
+    synthetic_division::<E>(&shifted, point)
+}
+
+/// Synthetic division of `p(x)` by `(x - point)`, dropping the remainder.
+///
+/// `p` is in ascending order (lowest degree first); the quotient is
+/// returned in the same order.
+fn synthetic_division<E: Pairing>(p: &[E::ScalarField], point: &E::ScalarField) -> Vec<E::ScalarField> {
+    if p.len() <= 1 {
+        return Vec::new();
+    }
+
+    let m = p.len() - 1;
+    let mut quotient = vec![E::ScalarField::from(0u64); m];
+    quotient[m - 1] = p[m];
+
+    for i in (1..m).rev() {
+        quotient[i - 1] = p[i] + quotient[i] * point;
+    }
+
+    quotient
+}
+
+/// Subtracts `b` from `a`, coefficient-wise (shorter operand is zero-padded).
+pub fn subtract_polynomials<E: Pairing>(
+    a: &[E::ScalarField],
+    b: &[E::ScalarField],
+) -> Vec<E::ScalarField> {
+    let len = a.len().max(b.len());
+    let mut result = vec![E::ScalarField::from(0u64); len];
+
+    for (i, coeff) in a.iter().enumerate() {
+        result[i] += coeff;
+    }
+    for (i, coeff) in b.iter().enumerate() {
+        result[i] -= coeff;
+    }
+
+    result
+}
+
+/// The vanishing polynomial `Z_S(x) = \prod_i (x - points[i])`.
+pub fn vanishing_polynomial<E: Pairing>(points: &[E::ScalarField]) -> Vec<E::ScalarField> {
+    let mut z = vec![E::ScalarField::from(1u64)];
+
+    for point in points {
+        // Multiply the running product by `(x - point)`.
+        let mut next = vec![E::ScalarField::from(0u64); z.len() + 1];
+        for (i, coeff) in z.iter().enumerate() {
+            next[i + 1] += coeff;
+            next[i] -= *coeff * point;
+        }
+        z = next;
+    }
+
+    z
+}
+
+/// Returns `true` if every element of `points` is pairwise distinct.
+///
+/// [`lagrange_interpolate`] and [`vanishing_polynomial`] assume this holds;
+/// callers that accept `points` from outside the crate should check it first
+/// and return a typed error rather than let interpolation panic.
+pub fn points_are_distinct<T: PartialEq>(points: &[T]) -> bool {
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            if points[i] == points[j] {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// The degree-`< points.len()` polynomial interpolating `(points[i], values[i])`,
+/// via Lagrange interpolation.
+pub fn lagrange_interpolate<E: Pairing>(
+    points: &[E::ScalarField],
+    values: &[E::ScalarField],
+) -> Vec<E::ScalarField> {
+    let mut result = vec![E::ScalarField::from(0u64); points.len()];
+
+    for i in 0..points.len() {
+        // L_i(x) = \prod_{j != i} (x - points[j]) / (points[i] - points[j])
+        let mut numerator = vec![E::ScalarField::from(1u64)];
+        let mut denominator = E::ScalarField::from(1u64);
+
+        for (j, point_j) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+
+            let mut next = vec![E::ScalarField::from(0u64); numerator.len() + 1];
+            for (k, coeff) in numerator.iter().enumerate() {
+                next[k + 1] += coeff;
+                next[k] -= *coeff * point_j;
+            }
+            numerator = next;
+            denominator *= points[i] - point_j;
+        }
+
+        let scale = values[i] * denominator.inverse().expect("points must be distinct");
+        for (k, coeff) in numerator.iter().enumerate() {
+            result[k] += *coeff * scale;
+        }
+    }
+
+    result
+}
+
+/// Exact polynomial long division of `numerator` by `denominator`, dropping
+/// the remainder. Assumes the division is exact.
+pub fn divide_polynomials<E: Pairing>(
+    numerator: &[E::ScalarField],
+    denominator: &[E::ScalarField],
+) -> Vec<E::ScalarField> {
+    let mut remainder = numerator.to_vec();
+    while remainder.last() == Some(&E::ScalarField::from(0u64)) {
+        remainder.pop();
+    }
+
+    let denom_degree = denominator.len() - 1;
+    let leading_inv = denominator[denom_degree]
+        .inverse()
+        .expect("denominator must have a non-zero leading coefficient");
+
+    if remainder.len() <= denom_degree {
+        return Vec::new();
+    }
+
+    let mut quotient = vec![E::ScalarField::from(0u64); remainder.len() - denom_degree];
+
+    for i in (0..quotient.len()).rev() {
+        let coeff = remainder[i + denom_degree] * leading_inv;
+        quotient[i] = coeff;
+
+        for (j, denom_coeff) in denominator.iter().enumerate() {
+            remainder[i + j] -= coeff * denom_coeff;
+        }
+    }
+
+    quotient
+}
@@ -3,10 +3,19 @@
 //! This module contains the implementation of an Extractable Witness Encryption from an Extractable Witness KEM.'
 
 #![allow(clippy::type_complexity)]
-use crate::kem::{KEMError, KEM};
+use crate::kem::{KEMError, KeyStream, KEM};
+use crate::pol_op::{evaluate_polynomial, lagrange_interpolate};
 use ark_ec::pairing::Pairing;
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::UniformRand;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
 use thiserror::Error;
 
+/// Length in bytes of the authentication tag produced by the authenticated
+/// encryption methods.
+const TAG_LEN: usize = 32;
+
 /// Extractable Witness Encryption struct.
 pub struct WE<E: Pairing> {
     kem: KEM<E>,
@@ -18,6 +27,11 @@ impl<E: Pairing> WE<E> {
         Self { kem }
     }
 
+    /// The underlying witness KEM.
+    pub fn kem(&self) -> &KEM<E> {
+        &self.kem
+    }
+
     /// Encrypts a message for a commitment and a set of points and values.
     /// Returns a vector of ciphertext tuples, in the order of the input points and values.
     pub fn encrypt(
@@ -37,6 +51,154 @@ impl<E: Pairing> WE<E> {
         Ok(cts)
     }
 
+    /// Encrypts a message to a *set* of points and required values, bound
+    /// together so that decryption only needs a single aggregated KZG
+    /// batch opening over the whole set, instead of one opening per point.
+    ///
+    /// Returns `(key_ct, msg_ct)`, decryptable with [`Self::decrypt_batch`]
+    /// given a batch witness for `points`/`values`.
+    pub fn encrypt_batch(
+        &self,
+        com: E::G1,
+        points: &[E::ScalarField],
+        values: &[E::ScalarField],
+        msg: &[u8],
+    ) -> Result<(E::G2, Vec<u8>), WEError> {
+        // (ct_1, k) <- Encap(x)
+        let (key_ct, mut key_stream) = self.kem.encapsulate_batch(com, points, values)?;
+
+        // ct_2 <- Enc(k, m)
+        let mut msg_ct = vec![0u8; msg.len()];
+        key_stream.fill(&mut msg_ct);
+        for i in 0..msg.len() {
+            msg_ct[i] ^= msg[i];
+        }
+
+        Ok((key_ct, msg_ct))
+    }
+
+    /// Decrypts a ciphertext produced by [`Self::encrypt_batch`] given a
+    /// single aggregated KZG batch witness `proof` for the whole set of
+    /// points the ciphertext was created for.
+    pub fn decrypt_batch(
+        &self,
+        proof: E::G1,
+        key_ct: E::G2,
+        msg_ct: &[u8],
+    ) -> Result<Vec<u8>, WEError> {
+        // k = Decap(w, ct_1)
+        let mut key_stream = self.kem.decapsulate_batch(proof, key_ct)?;
+
+        // m = Dec(k, ct_2)
+        let mut msg = vec![0u8; msg_ct.len()];
+        key_stream.fill(&mut msg);
+        for i in 0..msg_ct.len() {
+            msg[i] ^= msg_ct[i];
+        }
+
+        Ok(msg)
+    }
+
+    /// Encrypts a message so that it is only recoverable by a party who can
+    /// produce valid KZG openings for at least `t` of the `n` supplied
+    /// statements `(points[i], values[i])`.
+    ///
+    /// A random symmetric key is Shamir-shared across a degree `t - 1`
+    /// polynomial, each share is witness-encrypted to its own statement via
+    /// [`Self::encrypt_single`], and `msg` is encrypted under the shared
+    /// key. [`Self::combine_and_decrypt`] reverses this given `t` valid
+    /// openings.
+    pub fn encrypt_threshold(
+        &self,
+        com: E::G1,
+        points: &[E::ScalarField],
+        values: &[E::ScalarField],
+        msg: &[u8],
+        t: usize,
+    ) -> Result<ThresholdCiphertext<E>, WEError> {
+        let n = points.len();
+        if t == 0 || t > n {
+            return Err(WEError::InvalidThreshold);
+        }
+
+        let mut rng = ark_std::rand::thread_rng();
+
+        // f(x) = key + a_1 x + ... + a_{t-1} x^{t-1}, a degree t-1 polynomial
+        // with f(0) = key.
+        let key = E::ScalarField::rand(&mut rng);
+        let mut f = vec![key];
+        for _ in 1..t {
+            f.push(E::ScalarField::rand(&mut rng));
+        }
+
+        let mut share_cts = Vec::with_capacity(n);
+        for i in 0..n {
+            let x = E::ScalarField::from((i + 1) as u64);
+            let share = evaluate_polynomial::<E>(&f, &x);
+            let share_bytes = share.into_bigint().to_bytes_le();
+
+            let (key_ct, share_ct) = self.encrypt_single(com, points[i], values[i], &share_bytes)?;
+            share_cts.push((key_ct, share_ct));
+        }
+
+        let key_bytes = key.into_bigint().to_bytes_le();
+        let (payload_ct, tag) = symmetric_encrypt(&key_bytes, msg);
+
+        Ok(ThresholdCiphertext {
+            share_cts,
+            payload_ct,
+            tag,
+            t,
+        })
+    }
+
+    /// Recovers the message from a [`ThresholdCiphertext`] given at least
+    /// `ct.t` valid KZG openings, each identified by the index (into the
+    /// original `points`/`values` passed to [`Self::encrypt_threshold`]) of
+    /// the statement it opens.
+    ///
+    /// Returns [`WEError::InsufficientShares`] if fewer than `ct.t` *distinct,
+    /// in-range* openings are supplied (out-of-range indices and duplicate
+    /// indices are discarded rather than trusted, so a single opening
+    /// replayed `t` times cannot be used to pass the threshold), or
+    /// [`WEError::AuthenticationFailed`] if the recovered key does not match
+    /// the ciphertext (e.g. because an opening was invalid).
+    pub fn combine_and_decrypt(
+        &self,
+        ct: &ThresholdCiphertext<E>,
+        proofs: &[(usize, E::G1)],
+    ) -> Result<Vec<u8>, WEError> {
+        let mut seen = std::collections::HashSet::with_capacity(proofs.len());
+        let distinct_in_range: Vec<(usize, E::G1)> = proofs
+            .iter()
+            .filter(|(index, _)| *index < ct.share_cts.len())
+            .filter(|(index, _)| seen.insert(*index))
+            .copied()
+            .collect();
+
+        if distinct_in_range.len() < ct.t {
+            return Err(WEError::InsufficientShares);
+        }
+
+        let mut xs = Vec::with_capacity(ct.t);
+        let mut shares = Vec::with_capacity(ct.t);
+        for (index, proof) in distinct_in_range.into_iter().take(ct.t) {
+            let (key_ct, share_ct) = &ct.share_cts[index];
+            let share_bytes = self.decrypt_single(proof, *key_ct, share_ct)?;
+            let share = E::ScalarField::from_le_bytes_mod_order(&share_bytes);
+
+            xs.push(E::ScalarField::from((index + 1) as u64));
+            shares.push(share);
+        }
+
+        // f(0) is the constant term of the interpolated polynomial.
+        let f = lagrange_interpolate::<E>(&xs, &shares);
+        let key = f[0];
+        let key_bytes = key.into_bigint().to_bytes_le();
+
+        symmetric_decrypt(&key_bytes, &ct.payload_ct, &ct.tag)
+    }
+
     /// Encrypts a message using a commitment, point, and value.
     /// Returns two ciphertexts:
     /// - `key_ct`: used to generate the decryption key.
@@ -83,12 +245,161 @@ impl<E: Pairing> WE<E> {
 
         Ok(msg)
     }
+
+    /// Encrypts a message using a commitment, point, and value, in
+    /// authenticated mode.
+    ///
+    /// Unlike [`Self::encrypt_single`], which is a raw one-time-pad XOR and
+    /// silently yields garbage on a wrong opening, this derives an
+    /// encryption keystream *and* a separate MAC key from the encapsulated
+    /// key and binds them together with a tag `C3 = H(mac_key || msg_ct)`.
+    /// A wrong proof at decryption time is then detected and reported as
+    /// [`WEError::AuthenticationFailed`] instead of returning garbage.
+    ///
+    /// Returns `(key_ct, msg_ct, tag)`.
+    pub fn encrypt_authenticated(
+        &self,
+        com: E::G1,
+        point: E::ScalarField,
+        value: E::ScalarField,
+        msg: &[u8],
+    ) -> Result<(E::G2, Vec<u8>, Vec<u8>), WEError> {
+        // (ct_1, k) <- Encap(x)
+        let (key_ct, mut key_stream) = self.kem.encapsulate(com, point, value)?;
+
+        // Split the keystream into an encryption keystream and a MAC key.
+        let mut enc_keystream = vec![0u8; msg.len()];
+        key_stream.fill(&mut enc_keystream);
+        let mut mac_key = vec![0u8; TAG_LEN];
+        key_stream.fill(&mut mac_key);
+
+        // C2 = msg XOR keystream
+        let mut msg_ct = vec![0u8; msg.len()];
+        for i in 0..msg.len() {
+            msg_ct[i] = msg[i] ^ enc_keystream[i];
+        }
+
+        // C3 = H(mac_key || C2)
+        let tag = authentication_tag(&mac_key, &msg_ct);
+
+        Ok((key_ct, msg_ct, tag))
+    }
+
+    /// Decrypts a ciphertext produced by [`Self::encrypt_authenticated`],
+    /// verifying the authentication tag before returning the message.
+    ///
+    /// Returns [`WEError::AuthenticationFailed`] if `proof` does not open
+    /// `key_ct` to the statement the ciphertext was created for.
+    pub fn decrypt_authenticated(
+        &self,
+        proof: E::G1,
+        key_ct: E::G2,
+        msg_ct: &[u8],
+        tag: &[u8],
+    ) -> Result<Vec<u8>, WEError> {
+        // k = Decap(w, ct_1)
+        let mut key_stream = self.kem.decapsulate(proof, key_ct)?;
+
+        let mut enc_keystream = vec![0u8; msg_ct.len()];
+        key_stream.fill(&mut enc_keystream);
+        let mut mac_key = vec![0u8; TAG_LEN];
+        key_stream.fill(&mut mac_key);
+
+        let expected_tag = authentication_tag(&mac_key, msg_ct);
+        if !tags_match(&expected_tag, tag) {
+            return Err(WEError::AuthenticationFailed);
+        }
+
+        // m = Dec(k, ct_2)
+        let mut msg = vec![0u8; msg_ct.len()];
+        for i in 0..msg_ct.len() {
+            msg[i] = msg_ct[i] ^ enc_keystream[i];
+        }
+
+        Ok(msg)
+    }
+}
+
+pub(crate) fn authentication_tag(mac_key: &[u8], msg_ct: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(mac_key);
+    hasher.update(msg_ct);
+    hasher.finalize().to_vec()
+}
+
+/// Compares two tags in constant time, to avoid a timing side channel on
+/// MAC verification.
+pub(crate) fn tags_match(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Encrypts `msg` under a raw symmetric `key`, authenticated the same way
+/// as [`WE::encrypt_authenticated`]: the keystream derived from `key` is
+/// split into an encryption stream and a MAC key.
+fn symmetric_encrypt(key: &[u8], msg: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let mut key_stream = KeyStream::from_seed_bytes(key);
+
+    let mut enc_keystream = vec![0u8; msg.len()];
+    key_stream.fill(&mut enc_keystream);
+    let mut mac_key = vec![0u8; TAG_LEN];
+    key_stream.fill(&mut mac_key);
+
+    let mut msg_ct = vec![0u8; msg.len()];
+    for i in 0..msg.len() {
+        msg_ct[i] = msg[i] ^ enc_keystream[i];
+    }
+
+    let tag = authentication_tag(&mac_key, &msg_ct);
+    (msg_ct, tag)
+}
+
+/// Inverse of [`symmetric_encrypt`]; returns [`WEError::AuthenticationFailed`]
+/// if `tag` does not match.
+fn symmetric_decrypt(key: &[u8], msg_ct: &[u8], tag: &[u8]) -> Result<Vec<u8>, WEError> {
+    let mut key_stream = KeyStream::from_seed_bytes(key);
+
+    let mut enc_keystream = vec![0u8; msg_ct.len()];
+    key_stream.fill(&mut enc_keystream);
+    let mut mac_key = vec![0u8; TAG_LEN];
+    key_stream.fill(&mut mac_key);
+
+    let expected_tag = authentication_tag(&mac_key, msg_ct);
+    if !tags_match(&expected_tag, tag) {
+        return Err(WEError::AuthenticationFailed);
+    }
+
+    let mut msg = vec![0u8; msg_ct.len()];
+    for i in 0..msg_ct.len() {
+        msg[i] = msg_ct[i] ^ enc_keystream[i];
+    }
+
+    Ok(msg)
+}
+
+/// A message encrypted under [`WE::encrypt_threshold`], recoverable via
+/// [`WE::combine_and_decrypt`] given `t` valid openings.
+pub struct ThresholdCiphertext<E: Pairing> {
+    /// One witness-encrypted Shamir share per statement, in the order of
+    /// the `points`/`values` passed to `encrypt_threshold`.
+    pub share_cts: Vec<(E::G2, Vec<u8>)>,
+    /// `msg` encrypted under the Shamir-shared symmetric key.
+    pub payload_ct: Vec<u8>,
+    /// Authentication tag over `payload_ct`.
+    pub tag: Vec<u8>,
+    /// The number of shares required to recover the key.
+    pub t: usize,
 }
 
 #[derive(Error, Debug)]
 pub enum WEError {
     #[error("Key Encapsulation Error {0}")]
     KEMError(KEMError),
+    #[error("Authentication failed: the supplied proof does not match the ciphertext")]
+    AuthenticationFailed,
+    #[error("Threshold must be between 1 and the number of statements")]
+    InvalidThreshold,
+    #[error("Fewer than the required threshold of valid openings were supplied")]
+    InsufficientShares,
 }
 
 impl From<KEMError> for WEError {
@@ -222,4 +533,328 @@ mod tests {
 
         assert_ne!(msg.to_vec(), wrong_decrypted_msg);
     }
+
+    #[test]
+    fn test_encrypt_decrypt_authenticated() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let point: Fr = Fr::rand(rng);
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        // p(x) = 7 x^4 + 9 x^3 - 5 x^2 - 25 x - 24
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let val = evaluate_polynomial::<Bls12_381>(&p, &point);
+        let commitment = we.kem.kzg().commit(&p).unwrap();
+
+        let msg = b"helloworld";
+
+        let (key_ct, msg_ct, tag) = we
+            .encrypt_authenticated(commitment, point, val, msg)
+            .unwrap();
+
+        let proof = we.kem.kzg().open(&p, &point).unwrap();
+
+        let decrypted_msg = we
+            .decrypt_authenticated(proof, key_ct, &msg_ct, &tag)
+            .unwrap();
+
+        assert_eq!(msg.to_vec(), decrypted_msg);
+    }
+
+    #[test]
+    fn test_decrypt_authenticated_invalid_proof_fails() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let point: Fr = Fr::rand(rng);
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        // p(x) = 7 x^4 + 9 x^3 - 5 x^2 - 25 x - 24
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let val = evaluate_polynomial::<Bls12_381>(&p, &point);
+        let commitment = we.kem.kzg().commit(&p).unwrap();
+
+        let msg = b"helloworld";
+        let (key_ct, msg_ct, tag) = we
+            .encrypt_authenticated(commitment, point, val, msg)
+            .unwrap();
+
+        let wrong_point: Fr = Fr::rand(rng);
+        let invalid_proof = we.kem.kzg().open(&p, &wrong_point).unwrap();
+
+        let result = we.decrypt_authenticated(invalid_proof, key_ct, &msg_ct, &tag);
+
+        assert!(matches!(result, Err(WEError::AuthenticationFailed)));
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_batch() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        // p(x) = 7 x^4 + 9 x^3 - 5 x^2 - 25 x - 24
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let points = vec![Fr::rand(rng), Fr::rand(rng), Fr::rand(rng)];
+        let values: Vec<Fr> = points
+            .iter()
+            .map(|&point| evaluate_polynomial::<Bls12_381>(&p, &point))
+            .collect();
+        let commitment = we.kem.kzg().commit(&p).unwrap();
+
+        let msg = b"helloworld";
+
+        let (key_ct, msg_ct) = we
+            .encrypt_batch(commitment, &points, &values, msg)
+            .unwrap();
+
+        let proof = we.kem.kzg().open_batch(&p, &points).unwrap();
+
+        let decrypted_msg = we.decrypt_batch(proof, key_ct, &msg_ct).unwrap();
+
+        assert_eq!(msg.to_vec(), decrypted_msg);
+    }
+
+    #[test]
+    fn test_decrypt_batch_invalid_proof() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        // p(x) = 7 x^4 + 9 x^3 - 5 x^2 - 25 x - 24
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let points = vec![Fr::rand(rng), Fr::rand(rng), Fr::rand(rng)];
+        let values: Vec<Fr> = points
+            .iter()
+            .map(|&point| evaluate_polynomial::<Bls12_381>(&p, &point))
+            .collect();
+        let commitment = we.kem.kzg().commit(&p).unwrap();
+
+        let msg = b"helloworld";
+        let (key_ct, msg_ct) = we
+            .encrypt_batch(commitment, &points, &values, msg)
+            .unwrap();
+
+        // A batch opening for a different set of points should not recover
+        // the message.
+        let other_points = vec![Fr::rand(rng), Fr::rand(rng), Fr::rand(rng)];
+        let invalid_proof = we.kem.kzg().open_batch(&p, &other_points).unwrap();
+
+        let decrypted_msg = we.decrypt_batch(invalid_proof, key_ct, &msg_ct).unwrap();
+
+        assert_ne!(msg.to_vec(), decrypted_msg);
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_threshold() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        // p(x) = 7 x^4 + 9 x^3 - 5 x^2 - 25 x - 24
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let points = vec![Fr::rand(rng), Fr::rand(rng), Fr::rand(rng), Fr::rand(rng)];
+        let values: Vec<Fr> = points
+            .iter()
+            .map(|&point| evaluate_polynomial::<Bls12_381>(&p, &point))
+            .collect();
+        let commitment = we.kem.kzg().commit(&p).unwrap();
+
+        let msg = b"helloworld";
+        let t = 3;
+
+        let ct = we
+            .encrypt_threshold(commitment, &points, &values, msg, t)
+            .unwrap();
+
+        // Any t-of-n valid openings, e.g. indices 0, 2, 3, suffice.
+        let proofs: Vec<(usize, _)> = [0, 2, 3]
+            .iter()
+            .map(|&i| (i, we.kem.kzg().open(&p, &points[i]).unwrap()))
+            .collect();
+
+        let decrypted_msg = we.combine_and_decrypt(&ct, &proofs).unwrap();
+        assert_eq!(msg.to_vec(), decrypted_msg);
+    }
+
+    #[test]
+    fn test_combine_and_decrypt_insufficient_shares() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let points = vec![Fr::rand(rng), Fr::rand(rng), Fr::rand(rng), Fr::rand(rng)];
+        let values: Vec<Fr> = points
+            .iter()
+            .map(|&point| evaluate_polynomial::<Bls12_381>(&p, &point))
+            .collect();
+        let commitment = we.kem.kzg().commit(&p).unwrap();
+
+        let msg = b"helloworld";
+        let t = 3;
+
+        let ct = we
+            .encrypt_threshold(commitment, &points, &values, msg, t)
+            .unwrap();
+
+        // Only 2 of the required 3 openings.
+        let proofs: Vec<(usize, _)> = [0, 2]
+            .iter()
+            .map(|&i| (i, we.kem.kzg().open(&p, &points[i]).unwrap()))
+            .collect();
+
+        let result = we.combine_and_decrypt(&ct, &proofs);
+        assert!(matches!(result, Err(WEError::InsufficientShares)));
+    }
+
+    #[test]
+    fn test_combine_and_decrypt_rejects_duplicate_share_index() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let points = vec![Fr::rand(rng), Fr::rand(rng), Fr::rand(rng), Fr::rand(rng)];
+        let values: Vec<Fr> = points
+            .iter()
+            .map(|&point| evaluate_polynomial::<Bls12_381>(&p, &point))
+            .collect();
+        let commitment = we.kem.kzg().commit(&p).unwrap();
+
+        let msg = b"helloworld";
+        let t = 3;
+
+        let ct = we
+            .encrypt_threshold(commitment, &points, &values, msg, t)
+            .unwrap();
+
+        // A single valid opening, repeated t times, must not satisfy the
+        // threshold (nor panic via a zero Lagrange denominator).
+        let proof = we.kem.kzg().open(&p, &points[0]).unwrap();
+        let proofs = vec![(0, proof), (0, proof), (0, proof)];
+
+        let result = we.combine_and_decrypt(&ct, &proofs);
+        assert!(matches!(result, Err(WEError::InsufficientShares)));
+    }
+
+    #[test]
+    fn test_combine_and_decrypt_rejects_out_of_range_index() {
+        let rng = &mut test_rng();
+        let g1_gen = G1Projective::rand(rng);
+        let g2_gen = G2Projective::rand(rng);
+        let secret = Fr::rand(rng);
+        let max_degree = 10;
+        let kzg: KZG<Bls12_381> = KZG::setup(g1_gen, g2_gen, max_degree, secret);
+        let kem: KEM<Bls12_381> = KEM::new(kzg);
+        let we: WE<Bls12_381> = WE::new(kem);
+
+        let p = vec![
+            Fr::from(-24),
+            Fr::from(-25),
+            Fr::from(-5),
+            Fr::from(9),
+            Fr::from(7),
+        ];
+        let points = vec![Fr::rand(rng), Fr::rand(rng), Fr::rand(rng), Fr::rand(rng)];
+        let values: Vec<Fr> = points
+            .iter()
+            .map(|&point| evaluate_polynomial::<Bls12_381>(&p, &point))
+            .collect();
+        let commitment = we.kem.kzg().commit(&p).unwrap();
+
+        let msg = b"helloworld";
+        let t = 3;
+
+        let ct = we
+            .encrypt_threshold(commitment, &points, &values, msg, t)
+            .unwrap();
+
+        // Index 99 is out of range for a 4-statement ciphertext and must be
+        // discarded rather than panicking on `ct.share_cts[99]`.
+        let proofs: Vec<(usize, _)> = [0, 2]
+            .iter()
+            .map(|&i| (i, we.kem.kzg().open(&p, &points[i]).unwrap()))
+            .chain(std::iter::once((99, we.kem.kzg().open(&p, &points[0]).unwrap())))
+            .collect();
+
+        let result = we.combine_and_decrypt(&ct, &proofs);
+        assert!(matches!(result, Err(WEError::InsufficientShares)));
+    }
 }
\ No newline at end of file